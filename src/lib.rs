@@ -3,8 +3,13 @@
 extern crate error_chain;
 extern crate zip;
 extern crate xml;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
 
 mod errors;
+#[cfg(feature = "serde")]
+mod serde_de;
 
 use std::fs;
 use std::path::Path;
@@ -12,10 +17,34 @@ use std::io::{Read, Seek, SeekFrom, Cursor};
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use xml::ParserConfig;
 use xml::reader::{EventReader, XmlEvent};
 
 use errors::*;
 
+/// Tunable options for the underlying XML pull parser.
+///
+/// By default characters are coalesced (so a `<t>`/`<v>` text node arrives as
+/// a single event) and comments are ignored; whitespace is preserved so that
+/// significant spaces in strings survive. Set `trim_whitespace` to drop
+/// insignificant whitespace around text nodes.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    pub coalesce_characters: bool,
+    pub ignore_comments: bool,
+    pub trim_whitespace: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> ParserOptions {
+        ParserOptions {
+            coalesce_characters: true,
+            ignore_comments: true,
+            trim_whitespace: false,
+        }
+    }
+}
+
 // Skip BOM marker
 fn skip_bom<R: Read + Seek>(reader: &mut R) -> Result<()> {
     let mut buffer = [0; 4];
@@ -44,10 +73,182 @@ fn skip_bom<R: Read + Seek>(reader: &mut R) -> Result<()> {
     Ok(())
 }
 
+// Split an A1-style cell reference such as "C5" into its column and row.
+// The column is the leading alphabetic run decoded as a bijective base-26
+// number (A=1 .. Z=26, AA=27, ..), the row is the trailing decimal run.
+fn parse_reference(reference: &str) -> Option<(usize, usize)> {
+    let mut column = 0usize;
+    let mut letters = 0usize;
+    let bytes = reference.as_bytes();
+    for &byte in bytes {
+        match byte {
+            b'A'...b'Z' => {
+                column = column * 26 + ((byte - b'A') as usize + 1);
+                letters += 1;
+            }
+            b'a'...b'z' => {
+                column = column * 26 + ((byte - b'a') as usize + 1);
+                letters += 1;
+            }
+            _ => break,
+        }
+    }
+    if letters == 0 {
+        return None;
+    }
+    match usize::from_str(&reference[letters..]) {
+        Ok(row) => Some((column, row)),
+        Err(_) => None,
+    }
+}
+
+// Parse a `<dimension>` `ref` such as "A1:C10" (or a single "A1") into an
+// inclusive `(start_row, start_column, end_row, end_column)` bounding box.
+fn parse_dimension(reference: &str) -> Option<(usize, usize, usize, usize)> {
+    let mut parts = reference.split(':');
+    let (start_column, start_row) = parse_reference(parts.next()?)?;
+    match parts.next() {
+        Some(end) => {
+            let (end_column, end_row) = parse_reference(end)?;
+            Some((start_row, start_column, end_row, end_column))
+        }
+        None => Some((start_row, start_column, start_row, start_column)),
+    }
+}
+
+// Build a `Range` from the collected cells, preferring the declared
+// dimension and otherwise computing the bounding box from the cells.
+fn build_range(cells: &[Cell], dimension: Option<(usize, usize, usize, usize)>) -> Range {
+    let bounds = dimension.or_else(|| {
+        let mut iter = cells.iter();
+        iter.next().map(|first| {
+            let mut min_row = first.row;
+            let mut min_column = first.column;
+            let mut max_row = first.row;
+            let mut max_column = first.column;
+            for cell in cells.iter() {
+                if cell.row < min_row { min_row = cell.row; }
+                if cell.row > max_row { max_row = cell.row; }
+                if cell.column < min_column { min_column = cell.column; }
+                if cell.column > max_column { max_column = cell.column; }
+            }
+            (min_row, min_column, max_row, max_column)
+        })
+    });
+    match bounds {
+        Some((start_row, start_column, end_row, end_column)) => {
+            let width = end_column + 1 - start_column;
+            let height = end_row + 1 - start_row;
+            let mut store = vec![Value::Empty; width * height];
+            for cell in cells.iter() {
+                if cell.row < start_row || cell.column < start_column {
+                    continue;
+                }
+                let r = cell.row - start_row;
+                let c = cell.column - start_column;
+                if r < height && c < width {
+                    store[r * width + c] = cell.value.clone();
+                }
+            }
+            Range {
+                start_row: start_row,
+                start_column: start_column,
+                width: width,
+                height: height,
+                store: store,
+            }
+        }
+        None => Range {
+            start_row: 0,
+            start_column: 0,
+            width: 0,
+            height: 0,
+            store: Vec::new(),
+        },
+    }
+}
+
+// Resolve a captured text node into a `Value` given the cell's declared
+// type, a pending date format, and the shared-string table. Shared between
+// the eager and streaming worksheet readers.
+fn resolve_value(kind: &ValueType, text: String, date_format: &mut Option<String>,
+                 strings: &[String]) -> Result<Value> {
+    let value = match *kind {
+        ValueType::SharedString => {
+            let index = usize::from_str(&text)?;
+            Value::String(strings[index].clone())
+        }
+        ValueType::InlineString | ValueType::FormulaString => {
+            Value::String(text)
+        }
+        ValueType::Boolean => {
+            Value::Bool(text.trim() != "0")
+        }
+        ValueType::Error => {
+            Value::Error(text)
+        }
+        ValueType::Number => {
+            match date_format.take() {
+                Some(format) => {
+                    let serial = f64::from_str(&text)?;
+                    Value::DateTime(serial_to_datetime(serial, format))
+                }
+                None => {
+                    match i64::from_str(&text) {
+                        Ok(value) => { Value::Integer(value) }
+                        Err(_) => {
+                            let value = f64::from_str(&text)?;
+                            Value::Float(value)
+                        }
+                    }
+                }
+            }
+        }
+    };
+    Ok(value)
+}
+
+// Resolve a cell's style index to its date/time format string, if any.
+fn date_format_for(cell_formats: &[usize], number_formats: &HashMap<usize, String>,
+                   style: usize) -> Option<String> {
+    let numfmt_id = match cell_formats.get(style) {
+        Some(id) => *id,
+        None => return None,
+    };
+    if let Some(format) = builtin_date_format(numfmt_id) {
+        return Some(format.to_string());
+    }
+    if let Some(format) = number_formats.get(&numfmt_id) {
+        if custom_format_is_date(format) {
+            return Some(format.clone());
+        }
+    }
+    None
+}
+
 #[derive(Debug, Clone)]
 enum ValueType {
-    String,
+    SharedString,
+    InlineString,
+    FormulaString,
     Number,
+    Boolean,
+    Error,
+}
+
+/// A calendar date and time of day decoded from an Excel serial number.
+///
+/// The `format` field carries the number-format string the cell was styled
+/// with, so callers can reproduce the original presentation.
+#[derive(Debug, Clone)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+    pub hour: i64,
+    pub minute: i64,
+    pub second: i64,
+    pub format: String,
 }
 
 #[derive(Debug, Clone)]
@@ -55,9 +256,97 @@ pub enum Value {
     String(String),
     Integer(i64),
     Float(f64),
+    DateTime(DateTime),
+    Bool(bool),
+    Error(String),
     Empty,
 }
 
+// Gregorian date arithmetic, after Howard Hinnant's `chrono`-compatible
+// algorithms. `days` are counted from 1970-01-01.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+// Interpret an Excel serial number as a calendar date-time. Serial 0 is
+// 1899-12-31, so serial 1 is 1900-01-01; serials >= 60 are shifted back by
+// one to skip the fictional 1900-02-29 that Excel's 1900 leap-year bug
+// leaves in the sequence (serial 60 itself reads as 1900-02-28).
+fn serial_to_datetime(serial: f64, format: String) -> DateTime {
+    let mut days = serial.trunc() as i64;
+    if days >= 60 {
+        days -= 1;
+    }
+    let (year, month, day) = civil_from_days(days_from_civil(1899, 12, 31) + days);
+    let seconds = ((serial - serial.trunc()) * 86400.0).round() as i64;
+    DateTime {
+        year: year,
+        month: month,
+        day: day,
+        hour: seconds / 3600,
+        minute: (seconds % 3600) / 60,
+        second: seconds % 60,
+        format: format,
+    }
+}
+
+// The built-in number-format string for an id, limited to the date/time
+// formats we decode. Returns `None` for ids that are not dates.
+fn builtin_date_format(numfmt_id: usize) -> Option<&'static str> {
+    match numfmt_id {
+        14 => Some("mm-dd-yy"),
+        15 => Some("d-mmm-yy"),
+        16 => Some("d-mmm"),
+        17 => Some("mmm-yy"),
+        18 => Some("h:mm AM/PM"),
+        19 => Some("h:mm:ss AM/PM"),
+        20 => Some("h:mm"),
+        21 => Some("h:mm:ss"),
+        22 => Some("m/d/yy h:mm"),
+        45 => Some("mm:ss"),
+        46 => Some("[h]:mm:ss"),
+        47 => Some("mmss.0"),
+        _ => None,
+    }
+}
+
+// A custom format string denotes a date/time when it mentions any of the
+// date tokens outside of a quoted literal or colour/condition bracket.
+fn custom_format_is_date(format: &str) -> bool {
+    let mut in_quote = false;
+    let mut in_bracket = false;
+    for ch in format.chars() {
+        match ch {
+            '"' => in_quote = !in_quote,
+            '[' if !in_quote => in_bracket = true,
+            ']' if !in_quote => in_bracket = false,
+            _ if in_quote || in_bracket => {}
+            'y' | 'm' | 'd' | 'h' | 's' | 'Y' | 'M' | 'D' | 'H' | 'S' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
 struct Relation {
     target: String,
     kind: String,
@@ -74,29 +363,239 @@ pub struct Cell {
     pub value: Value,
 }
 
+/// A rectangular, random-access view over a worksheet's cells.
+///
+/// The range knows its bounding box (taken from the sheet's `<dimension>`
+/// when present, otherwise computed from the observed cells) and stores the
+/// values row-major so that a coordinate resolves in O(1).
+pub struct Range {
+    start_row: usize,
+    start_column: usize,
+    width: usize,
+    height: usize,
+    store: Vec<Value>,
+}
+
+impl Range {
+    /// Number of columns spanned by the range.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows spanned by the range.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The top-left corner as `(row, column)`.
+    pub fn start(&self) -> (usize, usize) {
+        (self.start_row, self.start_column)
+    }
+
+    /// The bottom-right corner as `(row, column)`.
+    pub fn end(&self) -> (usize, usize) {
+        if self.width == 0 || self.height == 0 {
+            (self.start_row, self.start_column)
+        }
+        else {
+            (self.start_row + self.height - 1, self.start_column + self.width - 1)
+        }
+    }
+
+    /// The value at an absolute `(row, column)`, or `None` when the
+    /// coordinate falls outside the range.
+    pub fn get(&self, row: usize, column: usize) -> Option<&Value> {
+        if row < self.start_row || column < self.start_column {
+            return None;
+        }
+        let r = row - self.start_row;
+        let c = column - self.start_column;
+        if r >= self.height || c >= self.width {
+            return None;
+        }
+        self.store.get(r * self.width + c)
+    }
+
+    /// Iterate over the range's rows, each as a slice of `width()` values.
+    pub fn rows(&self) -> ::std::slice::Chunks<Value> {
+        self.store.chunks(if self.width == 0 { 1 } else { self.width })
+    }
+}
+
 pub struct WorkSheet {
     pub cells: Vec<Cell>,
+    pub range: Range,
+}
+
+/// A lazy iterator over a worksheet's rows.
+///
+/// It drives the underlying `EventReader` one `next()` at a time, yielding a
+/// fully-parsed row on each `</row>` and holding the parsed cells of no more
+/// than the current row. The sheet's uncompressed XML is still read into
+/// memory in full before the first row is yielded.
+pub struct WorksheetRows<'a> {
+    reader: EventReader<Cursor<Vec<u8>>>,
+    strings: &'a [String],
+    cell_formats: &'a [usize],
+    number_formats: &'a HashMap<usize, String>,
+    row: usize,
+    column: usize,
+    kind: ValueType,
+    date_format: Option<String>,
+    capture_value: bool,
+    current: Vec<Cell>,
+    finished: bool,
 }
 
-pub struct WorkBook {
-    archive: zip::ZipArchive<fs::File>,
+impl<'a> Iterator for WorksheetRows<'a> {
+    type Item = Result<Vec<Cell>>;
+
+    fn next(&mut self) -> Option<Result<Vec<Cell>>> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            match self.reader.next() {
+                Ok(XmlEvent::StartElement {name, attributes, ..}) => {
+                    self.capture_value = false;
+                    if name.local_name == "row" {
+                        for attribute in attributes.iter() {
+                            if attribute.name.local_name == "r" {
+                                match usize::from_str(&attribute.value) {
+                                    Ok(value) => { self.row = value; }
+                                    Err(error) => {
+                                        self.finished = true;
+                                        return Some(Err(error.into()));
+                                    }
+                                }
+                            }
+                        }
+                        self.column = 0;
+                    }
+                    else if name.local_name == "c" {
+                        let mut reference = None;
+                        self.date_format = None;
+                        self.kind = ValueType::Number;
+                        for attribute in attributes.iter() {
+                            if attribute.name.local_name == "s" {
+                                if let Ok(style) = usize::from_str(&attribute.value) {
+                                    self.date_format = date_format_for(
+                                        self.cell_formats, self.number_formats, style);
+                                }
+                            }
+                            else if attribute.name.local_name == "t" {
+                                self.kind = match attribute.value.as_str() {
+                                    "s" => ValueType::SharedString,
+                                    "inlineStr" => ValueType::InlineString,
+                                    "str" => ValueType::FormulaString,
+                                    "b" => ValueType::Boolean,
+                                    "e" => ValueType::Error,
+                                    _ => ValueType::Number,
+                                };
+                            }
+                            else if attribute.name.local_name == "r" {
+                                reference = parse_reference(&attribute.value);
+                            }
+                        }
+                        match reference {
+                            Some((ref_column, ref_row)) => {
+                                self.column = ref_column;
+                                self.row = ref_row;
+                            }
+                            None => {
+                                self.column += 1;
+                            }
+                        }
+                    }
+                    else if name.local_name == "v" {
+                        self.capture_value = true;
+                    }
+                    else if name.local_name == "t" {
+                        if let ValueType::InlineString = self.kind {
+                            self.capture_value = true;
+                        }
+                    }
+                }
+                Ok(XmlEvent::Characters(text)) => {
+                    if self.capture_value {
+                        let value = resolve_value(
+                            &self.kind, text, &mut self.date_format, self.strings);
+                        match value {
+                            Ok(value) => {
+                                let cell = Cell {
+                                    row: self.row,
+                                    column: self.column,
+                                    value: value,
+                                };
+                                self.current.push(cell);
+                            }
+                            Err(error) => {
+                                self.finished = true;
+                                return Some(Err(error));
+                            }
+                        }
+                    }
+                }
+                Ok(XmlEvent::EndElement {name, ..}) => {
+                    if name.local_name == "row" {
+                        return Some(Ok(::std::mem::replace(&mut self.current, Vec::new())));
+                    }
+                }
+                Ok(XmlEvent::EndDocument) => {
+                    self.finished = true;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error.into()));
+                }
+            }
+        }
+    }
+}
+
+pub struct WorkBook<R: Read + Seek> {
+    archive: zip::ZipArchive<R>,
     strings: Vec<String>,
     relations: HashMap<String, Relation>,
     sheets: HashMap<String, Sheet>,
+    /// `numFmtId` for each `<xf>` in `<cellXfs>`, indexed by style (`s`) id.
+    cell_formats: Vec<usize>,
+    /// Custom `<numFmt>` format codes keyed by their `numFmtId`.
+    number_formats: HashMap<usize, String>,
+    options: ParserOptions,
 }
 
-impl WorkBook {
-    pub fn open<P: AsRef<Path>>(path: P) -> Result<WorkBook> {
+impl WorkBook<fs::File> {
+    /// Open a workbook from a file path.
+    pub fn open_path<P: AsRef<Path>>(path: P) -> Result<WorkBook<fs::File>> {
         let file = fs::File::open(path)?;
-        let archive = zip::ZipArchive::new(file)?;
+        WorkBook::new(file)
+    }
+}
+
+impl<R: Read + Seek> WorkBook<R> {
+    /// Open a workbook from any seekable reader, e.g. an in-memory
+    /// `Cursor<Vec<u8>>` of downloaded bytes.
+    pub fn new(reader: R) -> Result<WorkBook<R>> {
+        let archive = zip::ZipArchive::new(reader)?;
         Ok(WorkBook {
             archive: archive,
             strings: vec![],
             sheets: HashMap::new(),
             relations: HashMap::new(),
+            cell_formats: vec![],
+            number_formats: HashMap::new(),
+            options: ParserOptions::default(),
             })
     }
 
+    /// Replace the XML parser options used when reading the archive's parts.
+    pub fn set_parser_options(&mut self, options: ParserOptions) {
+        self.options = options;
+    }
+
     fn load_xml(&mut self, name: &str) -> Result<EventReader<Cursor<Vec<u8>>>> {
         let mut file = self.archive.by_name(name)?;
         // Unfortunaltely ZipFile does not support Seek and xml-rs does not support UTF BOM.
@@ -104,7 +603,11 @@ impl WorkBook {
         file.read_to_end(&mut buffer).unwrap();
         let mut file = Cursor::new(buffer);
         skip_bom(&mut file)?;
-        Ok(EventReader::new(file))
+        let config = ParserConfig::new()
+            .coalesce_characters(self.options.coalesce_characters)
+            .ignore_comments(self.options.ignore_comments)
+            .trim_whitespace(self.options.trim_whitespace);
+        Ok(config.create_reader(file))
     }
 
     fn load_relations<R: Read>(&mut self, reader: EventReader<R>) -> Result<()> {
@@ -194,6 +697,71 @@ impl WorkBook {
         Ok(())
     }
 
+    /// Read the cell-format table from `xl/styles.xml`.
+    fn load_styles(&mut self) -> Result<()> {
+        let mut path = String::new();
+        for (_, relation) in self.relations.iter() {
+            if relation.kind.ends_with("/styles") {
+                path = relation.target.clone();
+                break;
+            }
+        }
+        if path.is_empty() {
+            return Ok(());
+        }
+        let reader = self.load_xml(&path)?;
+        let mut in_cell_xfs = false;
+        for ev in reader {
+            match ev {
+                Ok(XmlEvent::StartElement {name, attributes, ..}) => {
+                    if name.local_name == "cellXfs" {
+                        in_cell_xfs = true;
+                    }
+                    else if name.local_name == "numFmt" {
+                        let mut id = None;
+                        let mut code = String::new();
+                        for attribute in attributes.iter() {
+                            if attribute.name.local_name == "numFmtId" {
+                                id = usize::from_str(&attribute.value).ok();
+                            }
+                            else if attribute.name.local_name == "formatCode" {
+                                code = attribute.value.clone();
+                            }
+                        }
+                        if let Some(id) = id {
+                            self.number_formats.insert(id, code);
+                        }
+                    }
+                    else if name.local_name == "xf" && in_cell_xfs {
+                        let mut id = 0usize;
+                        for attribute in attributes.iter() {
+                            if attribute.name.local_name == "numFmtId" {
+                                id = usize::from_str(&attribute.value).unwrap_or(0);
+                            }
+                        }
+                        self.cell_formats.push(id);
+                    }
+                }
+                Ok(XmlEvent::EndElement {name, ..}) => {
+                    if name.local_name == "cellXfs" {
+                        in_cell_xfs = false;
+                    }
+                }
+                Err(error) => {
+                    return Err(error.into());
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve a cell's style index to a date/time format string, if the
+    /// style's number format is a date format.
+    fn date_format_for_style(&self, style: usize) -> Option<String> {
+        date_format_for(&self.cell_formats, &self.number_formats, style)
+    }
+
     fn load_workbook(&mut self) -> Result<()> {
         let mut path = String::new();
         for (_, relation) in self.relations.iter() {
@@ -251,14 +819,23 @@ impl WorkBook {
         let reader = self.load_xml(target)?;
         let mut row = 0usize;
         let mut column = 0usize;
-        let mut kind = ValueType::String;
+        let mut kind = ValueType::Number;
+        let mut date_format: Option<String> = None;
         let mut capture_value = false;
-        let mut sheet = WorkSheet { cells: Vec::new() }; 
+        let mut cells: Vec<Cell> = Vec::new();
+        let mut dimension: Option<(usize, usize, usize, usize)> = None;
         for ev in reader {
             match ev {
                 Ok(XmlEvent::StartElement {name, attributes, ..}) => {
                     capture_value = false;
-                    if name.local_name == "row" {
+                    if name.local_name == "dimension" {
+                        for attribute in attributes.iter() {
+                            if attribute.name.local_name == "ref" {
+                                dimension = parse_dimension(&attribute.value);
+                            }
+                        }
+                    }
+                    else if name.local_name == "row" {
                         for attribute in attributes.iter() {
                             if attribute.name.local_name == "r" {
                                 row = usize::from_str(&attribute.value)?;
@@ -267,40 +844,59 @@ impl WorkBook {
                         column = 0;
                     }
                     else if name.local_name == "c" {
+                        let mut reference = None;
+                        date_format = None;
+                        // A cell with no `t` attribute is numeric per the
+                        // OOXML spec, so start from that default each cell.
+                        kind = ValueType::Number;
                         for attribute in attributes.iter() {
-                            if attribute.name.local_name == "t" {
-                                if attribute.value == "s" {
-                                    kind = ValueType::String;
-                                }
-                                else if attribute.value == "n" {
-                                    kind = ValueType::Number;
+                            if attribute.name.local_name == "s" {
+                                if let Ok(style) = usize::from_str(&attribute.value) {
+                                    date_format = self.date_format_for_style(style);
                                 }
                             }
+                            else if attribute.name.local_name == "t" {
+                                kind = match attribute.value.as_str() {
+                                    "s" => ValueType::SharedString,
+                                    "inlineStr" => ValueType::InlineString,
+                                    "str" => ValueType::FormulaString,
+                                    "b" => ValueType::Boolean,
+                                    "e" => ValueType::Error,
+                                    _ => ValueType::Number,
+                                };
+                            }
+                            else if attribute.name.local_name == "r" {
+                                reference = parse_reference(&attribute.value);
+                            }
+                        }
+                        // Recover the true coordinate from the `r` attribute so
+                        // that rows with omitted (empty) cells keep their column
+                        // alignment. Fall back to counting when `r` is absent.
+                        match reference {
+                            Some((ref_column, ref_row)) => {
+                                column = ref_column;
+                                row = ref_row;
+                            }
+                            None => {
+                                column += 1;
+                            }
                         }
-                        column += 1;
                     }
                     else if name.local_name == "v" {
                         capture_value = true;
                     }
+                    else if name.local_name == "t" {
+                        // Inline strings carry their text in a nested
+                        // `<is><t>` element rather than in `<v>`.
+                        if let ValueType::InlineString = kind {
+                            capture_value = true;
+                        }
+                    }
                 }
                 Ok(XmlEvent::Characters(text)) => {
                     if capture_value {
-                        let value = match kind {
-                            ValueType::String => {
-                                let index = usize::from_str(&text)?;
-                                Value::String(self.strings[index].clone())
-                            },
-                            ValueType::Number => {
-                                match i64::from_str(&text) {
-                                    Ok(value) => { Value::Integer(value) }
-                                    Err(_) => {
-                                        let value = f64::from_str(&text)?;
-                                        Value::Float(value)
-                                    }
-                                }
-                            }
-                        };
-                        sheet.cells.push(Cell { row: row, column: column, value: value });
+                        let value = resolve_value(&kind, text, &mut date_format, &self.strings)?;
+                        cells.push(Cell { row: row, column: column, value: value });
                     }
                 }
                 Err(error) => {
@@ -309,13 +905,58 @@ impl WorkBook {
                 _ => {}
             }
         }
-        Ok(sheet)
+        let range = build_range(&cells, dimension);
+        Ok(WorkSheet { cells: cells, range: range })
+    }
+
+    /// Stream a worksheet one row at a time instead of collecting it.
+    ///
+    /// The returned iterator yields a `Vec<Cell>` per row and discards it
+    /// before reading the next, so parsed cells are never collected for the
+    /// whole sheet; the sheet's uncompressed XML is buffered in full.
+    pub fn worksheet_rows(&mut self, name: &str) -> Result<WorksheetRows> {
+        let sheet_relation = self.sheets[name].relation.clone();
+        let target = self.relations[&sheet_relation].target.clone();
+        let reader = self.load_xml(&target)?;
+        Ok(WorksheetRows {
+            reader: reader,
+            strings: &self.strings,
+            cell_formats: &self.cell_formats,
+            number_formats: &self.number_formats,
+            row: 0,
+            column: 0,
+            kind: ValueType::Number,
+            date_format: None,
+            capture_value: false,
+            current: Vec::new(),
+            finished: false,
+        })
+    }
+
+    /// Deserialize a worksheet into a vector of records, using the first row
+    /// as the column-name header. Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn deserialize<T>(&mut self, name: &str) -> Result<Vec<T>>
+        where T: serde::de::DeserializeOwned
+    {
+        let sheet = self.load_worksheet(name)?;
+        let mut rows: Vec<Vec<Value>> = sheet.range.rows()
+            .map(|row| row.to_vec())
+            .collect();
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+        let header: Vec<String> = rows.remove(0).iter()
+            .map(serde_de::header_name)
+            .collect();
+        serde_de::from_rows(header, rows)
     }
 
     pub fn load(&mut self) -> Result<()> {
         self.load_relationships()?;
         self.load_workbook()?;
         self.load_shared_strings()?;
+        self.load_styles()?;
         Ok(())
     }
 }
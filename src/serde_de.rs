@@ -0,0 +1,235 @@
+//! Optional `serde` integration: deserialize worksheet rows into user
+//! structs, treating the first row as a header of column names.
+
+use std::fmt::Display;
+use std::vec;
+
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer, Visitor, MapAccess,
+                IntoDeserializer};
+
+use errors::{Error, ErrorKind, Result};
+use {DateTime, Value};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::from_kind(ErrorKind::Msg(msg.to_string()))
+    }
+}
+
+/// Format a decoded date-time the way a caller would expect to parse it back.
+fn format_datetime(datetime: &DateTime) -> String {
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            datetime.year, datetime.month, datetime.day,
+            datetime.hour, datetime.minute, datetime.second)
+}
+
+// Coerce a value into the scalar the target field asks for, mirroring what a
+// hand-written `match` over `Value` would accept.
+fn as_f64(value: &Value) -> Result<f64> {
+    match *value {
+        Value::Integer(i) => Ok(i as f64),
+        Value::Float(f) => Ok(f),
+        Value::Bool(b) => Ok(if b { 1.0 } else { 0.0 }),
+        Value::String(ref s) => s.parse().map_err(de::Error::custom),
+        ref other => Err(de::Error::custom(format!("cannot read {:?} as a number", other))),
+    }
+}
+
+fn as_i64(value: &Value) -> Result<i64> {
+    match *value {
+        Value::Integer(i) => Ok(i),
+        Value::Float(f) => Ok(f as i64),
+        Value::Bool(b) => Ok(if b { 1 } else { 0 }),
+        Value::String(ref s) => s.parse().map_err(de::Error::custom),
+        ref other => Err(de::Error::custom(format!("cannot read {:?} as an integer", other))),
+    }
+}
+
+fn as_bool(value: &Value) -> Result<bool> {
+    match *value {
+        Value::Bool(b) => Ok(b),
+        Value::Integer(i) => Ok(i != 0),
+        Value::String(ref s) => match s.trim() {
+            "1" | "true" | "TRUE" | "True" => Ok(true),
+            "0" | "false" | "FALSE" | "False" | "" => Ok(false),
+            other => Err(de::Error::custom(format!("cannot read {:?} as a bool", other))),
+        },
+        ref other => Err(de::Error::custom(format!("cannot read {:?} as a bool", other))),
+    }
+}
+
+fn as_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        Value::Error(s) => s,
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::DateTime(ref dt) => format_datetime(dt),
+        Value::Empty => String::new(),
+    }
+}
+
+/// A `Deserializer` over a single `Value`.
+struct ValueDeserializer {
+    value: Value,
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        match self.value {
+            Value::String(s) => visitor.visit_string(s),
+            Value::Integer(i) => visitor.visit_i64(i),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Error(s) => visitor.visit_string(s),
+            Value::DateTime(ref dt) => visitor.visit_string(format_datetime(dt)),
+            Value::Empty => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_bool(as_bool(&self.value)?)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_f64(as_f64(&self.value)?)
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_f32(as_f64(&self.value)? as f32)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_string(as_string(self.value))
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_i64(as_i64(&self.value)?)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_u64(as_i64(&self.value)? as u64)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        match self.value {
+            Value::Empty => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 u8 u16 u32 char bytes byte_buf unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+/// Deserialize one row (already paired with its column headers) into `T`.
+struct RecordDeserializer {
+    pairs: vec::IntoIter<(String, Value)>,
+    value: Option<Value>,
+}
+
+impl<'de> Deserializer<'de> for RecordDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_struct<V>(self, _name: &'static str, _fields: &'static [&'static str],
+                             visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct
+        enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for RecordDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+        where K: de::DeserializeSeed<'de>
+    {
+        match self.pairs.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+        where V: de::DeserializeSeed<'de>
+    {
+        let value = self.value.take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(ValueDeserializer { value: value })
+    }
+}
+
+/// Turn a header row and the data rows below it into a `Vec<T>`.
+pub fn from_rows<T>(header: Vec<String>, rows: Vec<Vec<Value>>) -> Result<Vec<T>>
+    where T: DeserializeOwned
+{
+    let mut records = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut pairs: Vec<(String, Value)> = Vec::with_capacity(header.len());
+        for (index, name) in header.iter().enumerate() {
+            let value = row.get(index).cloned().unwrap_or(Value::Empty);
+            pairs.push((name.clone(), value));
+        }
+        let deserializer = RecordDeserializer {
+            pairs: pairs.into_iter(),
+            value: None,
+        };
+        records.push(T::deserialize(deserializer)?);
+    }
+    Ok(records)
+}
+
+/// Read a column header cell as its name.
+pub fn header_name(value: &Value) -> String {
+    as_string(value.clone())
+}